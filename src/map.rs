@@ -4,6 +4,13 @@ mod room;
 mod tile_pos;
 mod tile_rect;
 mod tile;
+mod bsp;
+mod cave;
+mod connectivity;
+mod ascii;
+mod symmetry;
+mod history;
+mod corridor;
 
 pub use self::grid_size::*;
 pub use self::grid::*;
@@ -11,6 +18,13 @@ pub use self::room::*;
 pub use self::tile_pos::*;
 pub use self::tile_rect::*;
 pub use self::tile::*;
+pub use self::bsp::*;
+pub use self::cave::*;
+pub use self::connectivity::*;
+pub use self::ascii::*;
+pub use self::symmetry::*;
+pub use self::history::*;
+pub use self::corridor::*;
 
 use std::fmt;
 use std::cmp;
@@ -26,6 +40,40 @@ impl fmt::Display for RoomId {
     }
 }
 
+/// Which sides of a tile have a wall edge, used to decide where to draw wall sprites along the
+/// border of a room or passage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TileWalls {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
+}
+
+impl TileWalls {
+    /// Returns the walls that result from mirroring this tile across a vertical axis (left become
+    /// right and vice versa; top and bottom are unaffected)
+    pub fn flipped_horizontal(&self) -> Self {
+        Self {
+            top: self.top,
+            bottom: self.bottom,
+            left: self.right,
+            right: self.left,
+        }
+    }
+
+    /// Returns the walls that result from mirroring this tile across a horizontal axis (top and
+    /// bottom swap; left and right are unaffected)
+    pub fn flipped_vertical(&self) -> Self {
+        Self {
+            top: self.bottom,
+            bottom: self.top,
+            left: self.left,
+            right: self.right,
+        }
+    }
+}
+
 /// A type that represents the static floor plan of a map
 #[derive(Clone, PartialEq)]
 pub struct FloorMap {
@@ -61,6 +109,7 @@ impl fmt::Debug for FloorMap {
                             RoomType::Challenge => " ".on_red(),
                             RoomType::PlayerStart => " ".on_bright_blue(),
                             RoomType::TreasureChamber => " ".on_yellow(),
+                            RoomType::Cave => " ".on_green(),
                         }
                     },
                     Wall {..} => "\u{25a2}".on_black(),
@@ -221,3 +270,20 @@ impl FloorMap {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_walls_flip_horizontal_swaps_left_and_right() {
+        let walls = TileWalls {top: true, right: true, bottom: false, left: false};
+        assert_eq!(walls.flipped_horizontal(), TileWalls {top: true, right: false, bottom: false, left: true});
+    }
+
+    #[test]
+    fn tile_walls_flip_vertical_swaps_top_and_bottom() {
+        let walls = TileWalls {top: true, right: true, bottom: false, left: false};
+        assert_eq!(walls.flipped_vertical(), TileWalls {top: false, right: true, bottom: true, left: false});
+    }
+}
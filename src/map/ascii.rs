@@ -0,0 +1,227 @@
+//! ASCII serialization for `FloorMap`
+//!
+//! Lets a level be written out as plain text and read back in, which makes it possible to
+//! hand-author fixture levels and write deterministic regression tests without going through a
+//! random generator.
+//!
+//! The round trip is lossy, not exact: the legend can't tell a `Room` floor apart from a
+//! `Passageway` floor, so both serialize to `.` and `.` always deserializes back to a `Room`. It
+//! also can't encode a tile object's payload, so `from_ascii` fills in a placeholder payload for
+//! each object character (`$` becomes `Item::TreasureKey`, `e` becomes probability `1.0`, and
+//! `<`/`>` become gate id `0`) rather than recovering whatever the original payload was. What
+//! round-trips exactly is the grid's shape and, for every tile, whether it's a wall, empty, floor,
+//! or which of these object *kinds* sits on it.
+//!
+//! ## Legend
+//!
+//! | Char | Meaning |
+//! |------|---------|
+//! | `#`  | Wall |
+//! | `.`  | Room or passageway floor |
+//! | ` `  | Empty (unallocated) tile |
+//! | `<`  | `ToPrevLevel` gate |
+//! | `>`  | `ToNextLevel` gate |
+//! | `$`  | Chest |
+//! | `e`  | Enemy spawn |
+
+use std::fmt;
+
+use super::{FloorMap, GridSize, Item, TileObject, TilePos, TileRect, TileType};
+
+/// An error produced while parsing a map from its ASCII representation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text contained no lines at all
+    EmptyInput,
+    /// Not every line was the same length
+    RaggedLine {row: usize, expected_cols: usize, found_cols: usize},
+    /// A character did not match any entry in the legend
+    UnrecognizedChar {row: usize, col: usize, ch: char},
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::EmptyInput => write!(f, "input contained no lines"),
+            ParseError::RaggedLine {row, expected_cols, found_cols} => write!(f,
+                "line {} has {} columns, expected {} (all lines must be the same width)",
+                row, found_cols, expected_cols),
+            ParseError::UnrecognizedChar {row, col, ch} => write!(f,
+                "unrecognized character {:?} at row {}, col {}", ch, row, col),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FloorMap {
+    /// Renders this map as ASCII text using the legend documented on this module, one line per
+    /// row and one character per tile
+    pub fn to_ascii(&self) -> String {
+        let grid = self.grid();
+        let mut output = String::new();
+
+        for row in grid.rows() {
+            for tile in row {
+                output.push(tile_to_char(tile));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Parses ASCII text (as produced by [`to_ascii`](FloorMap::to_ascii)) into a `FloorMap`.
+    ///
+    /// Room boundaries are inferred by flood-filling contiguous floor characters (`.` and the
+    /// object characters, which all sit on floor) and registering the bounding rectangle of each
+    /// region with `add_room`.
+    pub fn from_ascii(text: &str, tile_size: u32) -> Result<FloorMap, ParseError> {
+        let lines: Vec<&str> = text.lines().collect();
+        if lines.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        let cols = lines[0].chars().count();
+        for (row, line) in lines.iter().enumerate() {
+            let found_cols = line.chars().count();
+            if found_cols != cols {
+                return Err(ParseError::RaggedLine {row, expected_cols: cols, found_cols});
+            }
+        }
+
+        let rows = lines.len();
+        let mut map = FloorMap::new(GridSize {rows, cols}, tile_size);
+
+        // Walls and empty tiles are written directly; floor tiles are deferred until after rooms
+        // have been inferred below, since every floor tile needs a `RoomId` to be set.
+        let mut pending_objects = Vec::new();
+        let mut is_floor = vec![vec![false; cols]; rows];
+
+        for (row, line) in lines.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                let pos = TilePos {row, col};
+
+                match ch {
+                    '#' => map.grid_mut().set(pos, TileType::Wall),
+                    ' ' => map.grid_mut().set(pos, TileType::Empty),
+                    '.' => is_floor[row][col] = true,
+                    '<' => { is_floor[row][col] = true; pending_objects.push((pos, TileObject::ToPrevLevel(0))); },
+                    '>' => { is_floor[row][col] = true; pending_objects.push((pos, TileObject::ToNextLevel(0))); },
+                    // The legend has no way to encode item type, so every chest round-trips as a
+                    // generic treasure key; callers that need a specific item should place it
+                    // themselves after loading.
+                    '$' => { is_floor[row][col] = true; pending_objects.push((pos, TileObject::Chest(Item::TreasureKey))); },
+                    'e' => { is_floor[row][col] = true; pending_objects.push((pos, TileObject::EnemySpawn {probability: 1.0})); },
+                    ch => return Err(ParseError::UnrecognizedChar {row, col, ch}),
+                }
+            }
+        }
+
+        for region in floor_regions(&is_floor) {
+            let boundary = bounding_rect(&region);
+            let room_id = map.add_room(boundary);
+
+            for pos in region {
+                map.grid_mut().set(pos, TileType::Room(room_id));
+            }
+        }
+
+        for (pos, object) in pending_objects {
+            map.grid_mut().get_mut(pos).place_object(object);
+        }
+
+        Ok(map)
+    }
+}
+
+fn tile_to_char(tile: &super::Tile) -> char {
+    if let Some(object) = &tile.object {
+        return match object {
+            TileObject::ToPrevLevel(_) => '<',
+            TileObject::ToNextLevel(_) => '>',
+            TileObject::Chest(_) => '$',
+            TileObject::EnemySpawn {..} => 'e',
+        };
+    }
+
+    match tile.ttype {
+        TileType::Wall => '#',
+        TileType::Empty => ' ',
+        TileType::Room(_) | TileType::Passageway => '.',
+    }
+}
+
+/// Flood-fills `is_floor`, returning each connected (4-directional) region of floor tiles
+fn floor_regions(is_floor: &[Vec<bool>]) -> Vec<Vec<TilePos>> {
+    let rows = is_floor.len();
+    let cols = is_floor[0].len();
+
+    let mut visited = vec![vec![false; cols]; rows];
+    let mut regions = Vec::new();
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if !is_floor[row][col] || visited[row][col] {
+                continue;
+            }
+
+            let mut region = Vec::new();
+            let mut stack = vec![(row, col)];
+            visited[row][col] = true;
+
+            while let Some((r, c)) = stack.pop() {
+                region.push(TilePos {row: r, col: c});
+
+                let candidates: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+                for (dr, dc) in candidates {
+                    let (nr, nc) = (r as isize + dr, c as isize + dc);
+                    if nr < 0 || nc < 0 || nr as usize >= rows || nc as usize >= cols {
+                        continue;
+                    }
+
+                    let (nr, nc) = (nr as usize, nc as usize);
+                    if is_floor[nr][nc] && !visited[nr][nc] {
+                        visited[nr][nc] = true;
+                        stack.push((nr, nc));
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+    }
+
+    regions
+}
+
+fn bounding_rect(region: &[TilePos]) -> TileRect {
+    let min_row = region.iter().map(|pos| pos.row).min().unwrap();
+    let max_row = region.iter().map(|pos| pos.row).max().unwrap();
+    let min_col = region.iter().map(|pos| pos.col).min().unwrap();
+    let max_col = region.iter().map(|pos| pos.col).max().unwrap();
+
+    TileRect::new(
+        TilePos {row: min_row, col: min_col},
+        GridSize {rows: max_row - min_row + 1, cols: max_col - min_col + 1},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_round_trip_preserves_walls_and_floors() {
+        let fixture = "\
+#######
+#.....#
+#.###.#
+#.....#
+#######
+";
+
+        let map = FloorMap::from_ascii(fixture, 32).expect("fixture should parse");
+        assert_eq!(map.to_ascii(), fixture);
+    }
+}
@@ -0,0 +1,168 @@
+//! Binary-space-partition room placement
+//!
+//! Recursively splits the map into smaller and smaller rectangles until each leaf is close to
+//! the minimum room size, then carves a single room inside each leaf and connects each room to
+//! the nearest room already joined by a passage. This produces a dungeon where rooms are evenly distributed across the whole grid,
+//! as opposed to generators that scatter rooms randomly and risk leaving large empty gaps.
+
+use rand::Rng;
+use rand::rngs::ThreadRng;
+
+use super::{FloorMap, GenerationHistory, GridSize, TilePos, TileRect, TileType};
+
+/// A rectangle of the grid that has not yet been split or turned into a room
+#[derive(Debug, Clone, Copy)]
+struct Partition {
+    top_left: TilePos,
+    size: GridSize,
+}
+
+impl Partition {
+    fn rows(&self) -> usize { self.size.rows }
+    fn cols(&self) -> usize { self.size.cols }
+
+    /// Splits this partition into two, either horizontally or vertically, at a random line that
+    /// leaves both halves at least `min_room` long. Returns `None` if no such line exists.
+    fn split(&self, min_room: usize, rng: &mut ThreadRng) -> Option<(Partition, Partition)> {
+        let can_split_horizontally = self.rows() >= min_room * 2 + 1;
+        let can_split_vertically = self.cols() >= min_room * 2 + 1;
+
+        if !can_split_horizontally && !can_split_vertically {
+            return None;
+        }
+
+        let split_horizontally = if can_split_horizontally && can_split_vertically {
+            rng.gen_bool(0.5)
+        } else {
+            can_split_horizontally
+        };
+
+        Some(if split_horizontally {
+            let split_at = rng.gen_range(min_room, self.rows() - min_room);
+
+            (
+                Partition {top_left: self.top_left, size: GridSize {rows: split_at, cols: self.cols()}},
+                Partition {
+                    top_left: TilePos {row: self.top_left.row + split_at, col: self.top_left.col},
+                    size: GridSize {rows: self.rows() - split_at, cols: self.cols()},
+                },
+            )
+        } else {
+            let split_at = rng.gen_range(min_room, self.cols() - min_room);
+
+            (
+                Partition {top_left: self.top_left, size: GridSize {rows: self.rows(), cols: split_at}},
+                Partition {
+                    top_left: TilePos {row: self.top_left.row, col: self.top_left.col + split_at},
+                    size: GridSize {rows: self.rows(), cols: self.cols() - split_at},
+                },
+            )
+        })
+    }
+
+    /// Picks a random sub-rectangle within this partition to become a room's boundary, leaving at
+    /// least one tile of wall between the room and the edge of the partition where there's room
+    /// to spare. A partition that is exactly `min_room` tiles along an axis has no slack left, so
+    /// the room simply fills it along that axis instead.
+    fn carve_room(&self, min_room: usize, rng: &mut ThreadRng) -> TileRect {
+        let room_rows = random_len(self.rows(), min_room, rng);
+        let room_cols = random_len(self.cols(), min_room, rng);
+
+        let row_offset = random_offset(self.rows(), room_rows, rng);
+        let col_offset = random_offset(self.cols(), room_cols, rng);
+
+        TileRect::new(
+            TilePos {row: self.top_left.row + row_offset, col: self.top_left.col + col_offset},
+            GridSize {rows: room_rows, cols: room_cols},
+        )
+    }
+}
+
+/// Picks a random room length along one axis, somewhere between `min_room` and `available`
+/// (leaving at least one tile of wall when `available` allows it)
+fn random_len(available: usize, min_room: usize, rng: &mut ThreadRng) -> usize {
+    let max_len = available.saturating_sub(1).max(min_room);
+    if max_len <= min_room {
+        min_room
+    } else {
+        rng.gen_range(min_room, max_len + 1)
+    }
+}
+
+/// Picks a random offset for a room of length `room_len` within an axis of length `available`
+fn random_offset(available: usize, room_len: usize, rng: &mut ThreadRng) -> usize {
+    let slack = available - room_len;
+    if slack == 0 {
+        0
+    } else {
+        rng.gen_range(0, slack)
+    }
+}
+
+/// Parameters that control the shape of a BSP-generated layout
+#[derive(Debug, Clone, Copy)]
+pub struct BspConfig {
+    /// The smallest a room (and the partition that contains it) is allowed to be along either
+    /// axis, not including its surrounding wall
+    pub min_room: usize,
+    /// Stop splitting once this many leaves have been produced, even if larger partitions could
+    /// still be divided further. `None` means split until the minimum size is reached everywhere.
+    pub target_leaves: Option<usize>,
+}
+
+impl FloorMap {
+    /// Fills this map with a binary-space-partition layout: the grid is recursively split into
+    /// rectangles, a room is carved inside each leaf partition, and the rooms are joined by
+    /// corridors (see `connect_rooms_spanning`) so the result is fully reachable.
+    ///
+    /// Should only be called on a freshly created, empty `FloorMap`.
+    pub fn generate_bsp(&mut self, config: BspConfig, history: &mut GenerationHistory) {
+        let mut rng = rand::thread_rng();
+
+        let dimensions = self.grid().dimensions();
+        // Inset by a one tile border so generated rooms never touch the edge of the map
+        let root = Partition {
+            top_left: TilePos {row: 1, col: 1},
+            size: GridSize {rows: dimensions.rows.saturating_sub(2), cols: dimensions.cols.saturating_sub(2)},
+        };
+
+        let mut leaves = Vec::new();
+        let mut pending = vec![root];
+
+        while let Some(partition) = pending.pop() {
+            // Count the partition just popped too: if it isn't split it becomes a leaf itself
+            let reached_target = self.target_reached(config.target_leaves, leaves.len() + pending.len() + 1);
+
+            match partition.split(config.min_room, &mut rng) {
+                Some((left, right)) if !reached_target => {
+                    pending.push(left);
+                    pending.push(right);
+                },
+                _ => leaves.push(partition),
+            }
+        }
+
+        // Carve a room in each leaf
+        let mut room_ids = Vec::with_capacity(leaves.len());
+        for leaf in leaves {
+            let boundary = leaf.carve_room(config.min_room, &mut rng);
+            let room_id = self.add_room(boundary);
+
+            for pos in boundary.tile_positions() {
+                self.grid_mut().set(pos, TileType::Room(room_id));
+            }
+
+            room_ids.push(room_id);
+        }
+        history.record(self);
+
+        // Connect the rooms into a single spanning structure so the result is fully reachable
+        // without needing to keep the BSP tree around after the split.
+        self.connect_rooms_spanning(&room_ids);
+        history.record(self);
+    }
+
+    fn target_reached(&self, target_leaves: Option<usize>, leaf_count: usize) -> bool {
+        target_leaves.map_or(false, |target| leaf_count >= target)
+    }
+}
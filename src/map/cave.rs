@@ -0,0 +1,184 @@
+//! Cellular-automata cave generation
+//!
+//! Produces winding, organic caverns instead of rectangular rooms: the grid starts out as random
+//! noise and is smoothed a handful of times until it settles into natural-looking walls and open
+//! space, much like Conway's Game of Life. The result is then reduced to a single connected
+//! cavern so it still fits the `FloorMap` room model.
+
+use rand::Rng;
+
+use super::{FloorMap, GenerationHistory, RoomType, TilePos, TileRect, TileType};
+
+/// Parameters that control the shape of a cellular-automata cave
+#[derive(Debug, Clone, Copy)]
+pub struct CaveConfig {
+    /// Probability that an interior tile starts out as a wall
+    pub wall_probability: f64,
+    /// Number of smoothing iterations to run before settling on a final layout
+    pub smoothing_iterations: usize,
+}
+
+impl Default for CaveConfig {
+    fn default() -> Self {
+        Self {
+            wall_probability: 0.45,
+            smoothing_iterations: 5,
+        }
+    }
+}
+
+impl FloorMap {
+    /// Fills this map with a single cellular-automata cavern.
+    ///
+    /// Should only be called on a freshly created, empty `FloorMap`.
+    pub fn generate_cave(&mut self, config: CaveConfig, history: &mut GenerationHistory) {
+        let mut rng = rand::thread_rng();
+        let dimensions = self.grid().dimensions();
+
+        // The boundary is only used to register the cavern with the room table (e.g. for minimap
+        // bookkeeping); the actual shape is defined entirely by the `Room` tiles within it.
+        let boundary = TileRect::new(TilePos {row: 0, col: 0}, dimensions);
+        let room_id = self.add_room(boundary);
+        self.room_mut(room_id).set_room_type(RoomType::Cave);
+
+        let mut walls = vec![vec![true; dimensions.cols]; dimensions.rows];
+        for row in 1..dimensions.rows.saturating_sub(1) {
+            for col in 1..dimensions.cols.saturating_sub(1) {
+                walls[row][col] = rng.gen_bool(config.wall_probability);
+            }
+        }
+        self.write_walls(&walls, room_id);
+        history.record(self);
+
+        for _ in 0..config.smoothing_iterations {
+            walls = smooth(&walls);
+            self.write_walls(&walls, room_id);
+            history.record(self);
+        }
+
+        let cavern_walls = largest_floor_region(&walls);
+        self.write_walls(&cavern_walls, room_id);
+        history.record(self);
+    }
+
+    /// Writes a boolean wall grid (`true` means wall) into this map's tiles, assigning every
+    /// floor tile to `room_id`
+    fn write_walls(&mut self, walls: &[Vec<bool>], room_id: super::RoomId) {
+        for (row, walls_row) in walls.iter().enumerate() {
+            for (col, &is_wall) in walls_row.iter().enumerate() {
+                let pos = TilePos {row, col};
+                let ttype = if is_wall { TileType::Wall } else { TileType::Room(room_id) };
+                self.grid_mut().set(pos, ttype);
+            }
+        }
+    }
+}
+
+/// Runs one smoothing iteration, reading entirely from `walls` so that updates within the same
+/// pass don't leak into one another. The outer border is left untouched so edges are always
+/// `Wall`, matching the border tiles that were never randomized to begin with.
+fn smooth(walls: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let rows = walls.len();
+    let cols = walls[0].len();
+
+    let mut next = walls.to_vec();
+    for row in 1..rows.saturating_sub(1) {
+        for col in 1..cols.saturating_sub(1) {
+            next[row][col] = wall_neighbor_count(walls, row, col) >= 5;
+        }
+    }
+
+    next
+}
+
+/// Counts how many of a tile's eight neighbors are walls, counting any neighbor that falls off
+/// the edge of the grid as a wall
+fn wall_neighbor_count(walls: &[Vec<bool>], row: usize, col: usize) -> usize {
+    let rows = walls.len();
+    let cols = walls[0].len();
+    let (row, col) = (row as isize, col as isize);
+
+    let mut count = 0;
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if dr == 0 && dc == 0 {
+                continue;
+            }
+
+            let (r, c) = (row + dr, col + dc);
+            let is_wall = r < 0 || c < 0 || r as usize >= rows || c as usize >= cols
+                || walls[r as usize][c as usize];
+
+            if is_wall {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Iterates over the in-grid (row, col) of all eight neighbors of a tile. Off-grid neighbors are
+/// simply not yielded, which is correct for flood-filling (an off-grid tile can't be part of a
+/// region) but not for wall-counting — see `wall_neighbor_count` for that.
+fn neighbors(row: usize, col: usize, rows: usize, cols: usize) -> impl Iterator<Item=(usize, usize)> {
+    let row = row as isize;
+    let col = col as isize;
+
+    (-1..=1).flat_map(move |dr| (-1..=1).filter_map(move |dc| {
+        if dr == 0 && dc == 0 {
+            return None;
+        }
+
+        let (r, c) = (row + dr, col + dc);
+        if r < 0 || c < 0 || r as usize >= rows || c as usize >= cols {
+            return None;
+        }
+
+        Some((r as usize, c as usize))
+    }))
+}
+
+/// Flood-fills the floor tiles in `walls`, returning a same-sized wall grid (`true` means wall)
+/// where only the largest connected region of floor tiles remains open; everything else
+/// (including small pockets) is turned into a wall
+fn largest_floor_region(walls: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let rows = walls.len();
+    let cols = walls[0].len();
+
+    let mut visited = vec![vec![false; cols]; rows];
+    let mut largest: Vec<(usize, usize)> = Vec::new();
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if walls[row][col] || visited[row][col] {
+                continue;
+            }
+
+            let mut region = Vec::new();
+            let mut stack = vec![(row, col)];
+            visited[row][col] = true;
+
+            while let Some((r, c)) = stack.pop() {
+                region.push((r, c));
+
+                for (nr, nc) in neighbors(r, c, rows, cols) {
+                    if !walls[nr][nc] && !visited[nr][nc] {
+                        visited[nr][nc] = true;
+                        stack.push((nr, nc));
+                    }
+                }
+            }
+
+            if region.len() > largest.len() {
+                largest = region;
+            }
+        }
+    }
+
+    let mut cavern_walls = vec![vec![true; cols]; rows];
+    for (r, c) in largest {
+        cavern_walls[r][c] = false;
+    }
+    cavern_walls
+}
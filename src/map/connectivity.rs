@@ -0,0 +1,94 @@
+//! Connectivity enforcement and exit placement
+//!
+//! Generators can leave behind tiles that the player could never actually reach (a room that
+//! only connects to a sibling that itself never got wired up, for example). This pass walks the
+//! map from a known-good starting point, prunes anything unreachable, and picks the most remote
+//! reachable tile as the location for the descent to the next level.
+
+use std::collections::VecDeque;
+
+use super::{FloorMap, GenerationHistory, TileObject, TilePos, TileType};
+
+impl FloorMap {
+    /// Flood fills the map starting from `start`, converting any `Room`/`Passageway` tile that
+    /// isn't reachable from `start` into a `Wall`, then places a `TileObject::ToNextLevel` at the
+    /// reachable tile that is farthest (in tile steps) from `start`.
+    ///
+    /// `start` should be the center of the `PlayerStart` room. `gate_id` is stored on the placed
+    /// `ToNextLevel` tile so it can later be paired up with a `ToPrevLevel` tile on the next
+    /// level.
+    ///
+    /// Returns the position of the tile the exit was placed on.
+    pub fn ensure_connectivity(&mut self, start: TilePos, gate_id: usize, history: &mut GenerationHistory) -> TilePos {
+        let dimensions = self.grid().dimensions();
+        let mut visited = vec![vec![false; dimensions.cols]; dimensions.rows];
+        let mut distances = vec![vec![0usize; dimensions.cols]; dimensions.rows];
+
+        visited[start.row][start.col] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        let mut farthest = start;
+        while let Some(pos) = queue.pop_front() {
+            if distances[pos.row][pos.col] > distances[farthest.row][farthest.col] {
+                farthest = pos;
+            }
+
+            for neighbor in self.walkable_neighbors(pos) {
+                if visited[neighbor.row][neighbor.col] {
+                    continue;
+                }
+
+                visited[neighbor.row][neighbor.col] = true;
+                distances[neighbor.row][neighbor.col] = distances[pos.row][pos.col] + 1;
+                queue.push_back(neighbor);
+            }
+        }
+
+        for row in 0..dimensions.rows {
+            for col in 0..dimensions.cols {
+                let pos = TilePos {row, col};
+                if visited[row][col] {
+                    continue;
+                }
+
+                if self.is_walkable(pos) {
+                    self.grid_mut().set(pos, TileType::Wall);
+                }
+            }
+        }
+
+        self.grid_mut().get_mut(farthest).place_object(TileObject::ToNextLevel(gate_id));
+        history.record(self);
+        farthest
+    }
+
+    fn is_walkable(&self, pos: TilePos) -> bool {
+        match self.grid().get(pos).ttype {
+            TileType::Room(_) | TileType::Passageway => true,
+            TileType::Wall | TileType::Empty => false,
+        }
+    }
+
+    /// Returns the in-bounds, walkable tiles directly adjacent (no diagonals) to `pos`
+    fn walkable_neighbors(&self, pos: TilePos) -> impl Iterator<Item=TilePos> + '_ {
+        let dimensions = self.grid().dimensions();
+
+        let deltas: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        deltas.into_iter().filter_map(move |(dr, dc)| {
+            let row = pos.row as isize + dr;
+            let col = pos.col as isize + dc;
+
+            if row < 0 || col < 0 || row as usize >= dimensions.rows || col as usize >= dimensions.cols {
+                return None;
+            }
+
+            let neighbor = TilePos {row: row as usize, col: col as usize};
+            if self.is_walkable(neighbor) {
+                Some(neighbor)
+            } else {
+                None
+            }
+        })
+    }
+}
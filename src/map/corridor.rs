@@ -0,0 +1,89 @@
+//! Explicit L-shaped corridor carving
+//!
+//! Generators that place rooms independently (BSP leaves, hand-placed rooms, ...) need a way to
+//! punch a walkable tunnel between two of them afterwards. This carves an L-shaped passage
+//! between two room centers: a straight run along one row, then a straight run along one column,
+//! with the order of the two legs chosen at random so corridors don't all bend the same way.
+
+use rand::Rng;
+
+use super::{FloorMap, RoomId, TilePos, TileType};
+
+impl FloorMap {
+    /// Carves an L-shaped passage between `from` and `to`, picking at random whether the
+    /// horizontal or vertical leg comes first. Every non-room tile the tunnel passes through
+    /// becomes a `Passageway` and has its `TileWalls` cleared so the corridor isn't blocked by
+    /// leftover wall edges from the tiles it punches through; tiles that are already part of a
+    /// room are left as they are.
+    pub fn carve_corridor(&mut self, from: TilePos, to: TilePos) {
+        let corner = if rand::thread_rng().gen_bool(0.5) {
+            TilePos {row: from.row, col: to.col}
+        } else {
+            TilePos {row: to.row, col: from.col}
+        };
+
+        self.carve_straight(from, corner);
+        self.carve_straight(corner, to);
+    }
+
+    /// Connects every room in `room_ids` into a single connected structure by repeatedly linking
+    /// the next room to the nearest room already joined to the structure (starting from
+    /// `room_ids[0]`), rather than to the nearest room overall. This is what guarantees the
+    /// result is fully reachable: picking the globally nearest room for each room independently
+    /// can leave two mutually-nearest rooms linked only to each other, stranding them from
+    /// everything else.
+    pub fn connect_rooms_spanning(&mut self, room_ids: &[RoomId]) {
+        if room_ids.len() < 2 {
+            return;
+        }
+
+        let mut connected = vec![room_ids[0]];
+        for &room_id in &room_ids[1..] {
+            let center = self.room(room_id).boundary().center();
+
+            let nearest = connected.iter().copied()
+                .min_by_key(|&other| manhattan_distance(center, self.room(other).boundary().center()))
+                .expect("connected is never empty");
+
+            let nearest_center = self.room(nearest).boundary().center();
+            self.carve_corridor(center, nearest_center);
+
+            connected.push(room_id);
+        }
+    }
+
+    /// Carves a straight run of passageway tiles between two positions that share either a row or
+    /// a column
+    fn carve_straight(&mut self, from: TilePos, to: TilePos) {
+        debug_assert!(from.row == to.row || from.col == to.col,
+            "bug: carve_straight expects two positions sharing a row or column");
+
+        if from.row == to.row {
+            let (start, end) = (from.col.min(to.col), from.col.max(to.col));
+            for col in start..=end {
+                self.clear_for_passage(TilePos {row: from.row, col});
+            }
+        } else {
+            let (start, end) = (from.row.min(to.row), from.row.max(to.row));
+            for row in start..=end {
+                self.clear_for_passage(TilePos {row, col: from.col});
+            }
+        }
+    }
+
+    fn clear_for_passage(&mut self, pos: TilePos) {
+        // Don't overwrite floor tiles that are already part of a room; only punch through walls
+        // and empty space.
+        if let TileType::Room(_) = self.grid().get(pos).ttype {
+            return;
+        }
+
+        self.grid_mut().set(pos, TileType::Passageway);
+        self.grid_mut().get_mut(pos).walls = Default::default();
+    }
+}
+
+fn manhattan_distance(a: TilePos, b: TilePos) -> usize {
+    (a.row as isize - b.row as isize).unsigned_abs() as usize
+        + (a.col as isize - b.col as isize).unsigned_abs() as usize
+}
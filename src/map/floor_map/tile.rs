@@ -17,6 +17,10 @@ pub enum TileType {
     Passageway,
     /// Tiles that are part of a given room
     Room(RoomId),
+    /// Solid, unwalkable tiles that separate rooms and passages
+    Wall,
+    /// Tiles that have not been assigned to a room or passage yet
+    Empty,
 }
 
 /// The object or item placed at a particular tile
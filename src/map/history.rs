@@ -0,0 +1,50 @@
+//! Generation snapshot history
+//!
+//! Lets a developer step through exactly how a level was built by recording a clone of the
+//! `FloorMap` after each major step of generation (room placement, corridor carving, a smoothing
+//! iteration, the connectivity pass, ...). Recording is gated behind the `map-gen-history`
+//! feature so release builds that never open the visualizer pay nothing for it.
+
+use super::FloorMap;
+
+/// Accumulates a history of `FloorMap` snapshots taken over the course of generating a single
+/// level. Pass `&mut GenerationHistory` through a generation pipeline and call
+/// [`record`](GenerationHistory::record) after each major step.
+#[derive(Debug, Clone, Default)]
+pub struct GenerationHistory {
+    #[cfg(feature = "map-gen-history")]
+    snapshots: Vec<FloorMap>,
+}
+
+impl GenerationHistory {
+    /// Creates an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a clone of `map` as the next snapshot in the history. A no-op unless the
+    /// `map-gen-history` feature is enabled.
+    #[cfg_attr(not(feature = "map-gen-history"), allow(unused_variables))]
+    pub fn record(&mut self, map: &FloorMap) {
+        #[cfg(feature = "map-gen-history")]
+        self.snapshots.push(map.clone());
+    }
+
+    /// Returns every snapshot recorded so far, in the order they were taken. Always empty
+    /// unless the `map-gen-history` feature is enabled.
+    pub fn snapshots(&self) -> &[FloorMap] {
+        #[cfg(feature = "map-gen-history")]
+        { &self.snapshots }
+
+        #[cfg(not(feature = "map-gen-history"))]
+        { &[] }
+    }
+
+    /// Renders the snapshot at `index` using `FloorMap`'s alternate (colored, ASCII-art) `Debug`
+    /// output, the same view used to inspect a finished map. Returns `None` if `index` is out of
+    /// bounds, which is always the case when the `map-gen-history` feature is disabled since
+    /// `snapshots()` is then always empty.
+    pub fn render(&self, index: usize) -> Option<String> {
+        self.snapshots().get(index).map(|snapshot| format!("{:#?}", snapshot))
+    }
+}
@@ -0,0 +1,49 @@
+use super::TileRect;
+
+/// The kind of room a `Room` is, used to decide how it's rendered and how it behaves in gameplay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomType {
+    /// An ordinary room with no special behavior
+    Normal,
+    /// A room that spawns tougher enemies or otherwise tests the player
+    Challenge,
+    /// The room the player starts the level in
+    PlayerStart,
+    /// A room that holds treasure
+    TreasureChamber,
+    /// An organic cavern produced by the cellular-automata generator, as opposed to a
+    /// rectangular, hand-shaped room
+    Cave,
+}
+
+/// A single room on a `FloorMap`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Room {
+    boundary: TileRect,
+    room_type: RoomType,
+}
+
+impl Room {
+    /// Creates a new, normal room with the given boundary
+    pub(in super) fn new(boundary: TileRect) -> Self {
+        Self {
+            boundary,
+            room_type: RoomType::Normal,
+        }
+    }
+
+    /// Returns the rectangle of tiles this room occupies
+    pub fn boundary(&self) -> TileRect {
+        self.boundary
+    }
+
+    /// Returns the kind of room this is
+    pub fn room_type(&self) -> RoomType {
+        self.room_type
+    }
+
+    /// Changes the kind of room this is
+    pub(in super) fn set_room_type(&mut self, room_type: RoomType) {
+        self.room_type = room_type;
+    }
+}
@@ -0,0 +1,146 @@
+//! Symmetry modifier
+//!
+//! A cheap post-processing pass that mirrors a finished `FloorMap` across one or both axes,
+//! turning any base layout into a visually balanced, symmetric dungeon.
+
+use super::{FloorMap, TilePos, TileRect, TileType};
+
+/// Which axis (or axes) a map should be mirrored across
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Mirror the left half of the map onto the right half
+    Horizontal,
+    /// Mirror the top half of the map onto the bottom half
+    Vertical,
+    /// Apply both the horizontal and vertical mirror
+    Both,
+}
+
+impl FloorMap {
+    /// Mirrors this map in place according to `symmetry`. Tiles that get duplicated are
+    /// re-registered as fresh rooms via `add_room` rather than reusing the `RoomId` of the
+    /// original half, since the mirrored copy is a distinct room for gameplay purposes (spawns,
+    /// minimap labels, etc.).
+    pub fn apply_symmetry(&mut self, symmetry: Symmetry) {
+        match symmetry {
+            Symmetry::Horizontal => self.mirror_horizontal(),
+            Symmetry::Vertical => self.mirror_vertical(),
+            Symmetry::Both => {
+                self.mirror_horizontal();
+                self.mirror_vertical();
+            },
+        }
+    }
+
+    fn mirror_horizontal(&mut self) {
+        let dimensions = self.grid().dimensions();
+        let cols = dimensions.cols;
+
+        for row in 0..dimensions.rows {
+            for col in 0..cols / 2 {
+                let from = TilePos {row, col};
+                let to = TilePos {row, col: cols - 1 - col};
+                self.mirror_tile(from, to, |walls| walls.flipped_horizontal());
+            }
+        }
+
+        self.remap_mirrored_rooms(|pos| pos.col >= cols - cols / 2);
+    }
+
+    fn mirror_vertical(&mut self) {
+        let dimensions = self.grid().dimensions();
+        let rows = dimensions.rows;
+
+        for row in 0..rows / 2 {
+            for col in 0..dimensions.cols {
+                let from = TilePos {row, col};
+                let to = TilePos {row: rows - 1 - row, col};
+                self.mirror_tile(from, to, |walls| walls.flipped_vertical());
+            }
+        }
+
+        self.remap_mirrored_rooms(|pos| pos.row >= rows - rows / 2);
+    }
+
+    /// Copies the tile at `from` onto `to`, flipping its `TileWalls` with `flip` so corridors and
+    /// room edges stay consistent on the mirrored side
+    fn mirror_tile(&mut self, from: TilePos, to: TilePos, flip: impl Fn(&super::TileWalls) -> super::TileWalls) {
+        let source = self.grid().get(from).clone();
+        let dest = self.grid_mut().get_mut(to);
+
+        dest.ttype = source.ttype;
+        dest.object = source.object;
+        dest.walls = flip(&source.walls);
+        dest.texture_id = source.texture_id;
+    }
+
+    /// After mirroring, every room tile on the mirrored half still points at its original-half
+    /// `RoomId`. This walks those tiles and gives each distinct original room a fresh `RoomId` on
+    /// the mirrored side, registered with the mirrored `TileRect` boundary.
+    fn remap_mirrored_rooms(&mut self, on_mirrored_half: impl Fn(TilePos) -> bool) {
+        use std::collections::HashMap;
+
+        let dimensions = self.grid().dimensions();
+        let mut remapped = HashMap::new();
+        let mut positions_by_original: HashMap<_, Vec<TilePos>> = HashMap::new();
+
+        for row in 0..dimensions.rows {
+            for col in 0..dimensions.cols {
+                let pos = TilePos {row, col};
+                if !on_mirrored_half(pos) {
+                    continue;
+                }
+
+                if let TileType::Room(room_id) = self.grid().get(pos).ttype {
+                    positions_by_original.entry(room_id).or_default().push(pos);
+                }
+            }
+        }
+
+        for (original_room_id, positions) in positions_by_original {
+            let room_id = *remapped.entry(original_room_id).or_insert_with(|| {
+                let boundary = bounding_rect(&positions);
+                self.add_room(boundary)
+            });
+
+            for pos in positions {
+                self.grid_mut().get_mut(pos).ttype = TileType::Room(room_id);
+            }
+        }
+    }
+}
+
+fn bounding_rect(positions: &[TilePos]) -> TileRect {
+    let min_row = positions.iter().map(|pos| pos.row).min().unwrap();
+    let max_row = positions.iter().map(|pos| pos.row).max().unwrap();
+    let min_col = positions.iter().map(|pos| pos.col).min().unwrap();
+    let max_col = positions.iter().map(|pos| pos.col).max().unwrap();
+
+    TileRect::new(
+        TilePos {row: min_row, col: min_col},
+        super::GridSize {rows: max_row - min_row + 1, cols: max_col - min_col + 1},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::TileWalls;
+
+    #[test]
+    fn mirroring_horizontal_flips_a_corridor_edge_to_the_correct_side() {
+        let mut map = FloorMap::new(super::super::GridSize {rows: 1, cols: 4}, 32);
+
+        // A passageway tile whose right-hand edge is a wall
+        let pos = TilePos {row: 0, col: 1};
+        map.grid_mut().set(pos, TileType::Passageway);
+        map.grid_mut().get_mut(pos).walls = TileWalls {right: true, ..Default::default()};
+
+        map.apply_symmetry(Symmetry::Horizontal);
+
+        // The mirrored tile is in column 2 (cols - 1 - 1 = 2); its left-hand edge
+        // should now be the wall, since mirroring swaps which side the corridor is blocked on.
+        let mirrored = map.grid().get(TilePos {row: 0, col: 2}).walls;
+        assert_eq!(mirrored, TileWalls {left: true, ..Default::default()});
+    }
+}